@@ -0,0 +1,234 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits, types and structs to support a bounded `BTreeMap`.
+
+use sp_std::prelude::*;
+use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, marker::PhantomData};
+use codec::{Encode, Decode};
+use core::ops::Deref;
+use crate::traits::{Get, MaxEncodedLen};
+
+/// A bounded map based on a B-Tree.
+///
+/// B-Trees represent a fundamentally different data structure than a `Vec`. They guarantee that
+/// their contents are sorted according to the ordering of their `K` type, rather than insertion
+/// order, and that lookups for a specific key are O(log n).
+///
+/// Unlike a normal `BTreeMap`, the number of entries is bounded by `S`: the bound is enforced by
+/// `try_insert`, `TryFrom` and `Decode`. Read-only operations are deferred to the inner `BTreeMap`
+/// through `Deref`.
+#[derive(Encode)]
+pub struct BoundedBTreeMap<K, V, S>(BTreeMap<K, V>, PhantomData<S>);
+
+impl<K, V, S> Decode for BoundedBTreeMap<K, V, S>
+where
+	K: Decode + Ord,
+	V: Decode,
+	S: Get<u32>,
+{
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		// Start by reading the compact-encoded length prefix, exactly as `BTreeMap<K, V>` would,
+		// so we can reject an over-long encoding before allocating or decoding a single entry.
+		let len = <codec::Compact<u32>>::decode(input)?.0 as usize;
+		if len > Self::bound() {
+			return Err("BoundedBTreeMap exceeds its limit".into())
+		}
+
+		let mut inner = BTreeMap::new();
+		for _ in 0..len {
+			let (k, v) = <(K, V)>::decode(input)?;
+			inner.insert(k, v);
+		}
+		Ok(unsafe { Self::unchecked_from(inner) })
+	}
+}
+
+impl<K, V, S> BoundedBTreeMap<K, V, S> {
+	/// Create `Self` from `t` without any checks.
+	unsafe fn unchecked_from(t: BTreeMap<K, V>) -> Self {
+		Self(t, Default::default())
+	}
+
+	/// Consume self, and return the inner `BTreeMap`. Henceforth, the `BTreeMap<_>` can be
+	/// altered in an arbitrary way. At some point, if the reverse conversion is required, `TryFrom`
+	/// can be used.
+	///
+	/// This is useful for cases if you need access to an internal API of the inner `BTreeMap<_>`
+	/// which is not provided by the wrapper `BoundedBTreeMap`.
+	pub fn into_inner(self) -> BTreeMap<K, V> {
+		self.0
+	}
+}
+
+impl<K: Ord, V, S: Get<u32>> BoundedBTreeMap<K, V, S> {
+	/// Get the bound of the type in `usize`.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Exactly the same semantics as `BTreeMap::insert`, but returns an `Err` (and is a noop) if
+	/// the new length of the map exceeds `S` while inserting a *new* key.
+	///
+	/// Note that an `Ok(None)` result means that the item was inserted and `Ok(Some(_))` means
+	/// that, as opposed to the bound being reached, the item was already present, and has now
+	/// been updated.
+	pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, ()> {
+		if self.0.len() < Self::bound() || self.0.contains_key(&key) {
+			Ok(self.0.insert(key, value))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<K, V, S> Default for BoundedBTreeMap<K, V, S> {
+	fn default() -> Self {
+		// the bound cannot be below 0, which is satisfied by an empty map
+		unsafe { Self::unchecked_from(BTreeMap::default()) }
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> sp_std::fmt::Debug for BoundedBTreeMap<K, V, S>
+where
+	BTreeMap<K, V>: sp_std::fmt::Debug,
+	S: Get<u32>,
+{
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+		f.debug_tuple("BoundedBTreeMap").field(&self.0).field(&S::get()).finish()
+	}
+}
+
+impl<K, V, S> Clone for BoundedBTreeMap<K, V, S>
+where
+	BTreeMap<K, V>: Clone,
+{
+	fn clone(&self) -> Self {
+		// bound is retained
+		unsafe { Self::unchecked_from(self.0.clone()) }
+	}
+}
+
+impl<K, V, S: Get<u32>> TryFrom<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	type Error = ();
+	fn try_from(t: BTreeMap<K, V>) -> Result<Self, Self::Error> {
+		if t.len() <= Self::bound() {
+			// explicit check just above
+			Ok(unsafe { Self::unchecked_from(t) })
+		} else {
+			Err(())
+		}
+	}
+}
+
+// It is okay to give a non-mutable reference of the inner map to anyone.
+impl<K, V, S> AsRef<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	fn as_ref(&self) -> &BTreeMap<K, V> {
+		&self.0
+	}
+}
+
+// will allow for immutable all operations of `BTreeMap<K, V>` on `BoundedBTreeMap<K, V>`.
+impl<K, V, S> Deref for BoundedBTreeMap<K, V, S> {
+	type Target = BTreeMap<K, V>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<K, V, S> sp_std::iter::IntoIterator for BoundedBTreeMap<K, V, S> {
+	type Item = (K, V);
+	type IntoIter = sp_std::collections::btree_map::IntoIter<K, V>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<K, V, S> PartialEq for BoundedBTreeMap<K, V, S>
+where
+	BTreeMap<K, V>: PartialEq,
+{
+	fn eq(&self, rhs: &Self) -> bool {
+		self.0 == rhs.0
+	}
+}
+
+impl<K, V, S> Eq for BoundedBTreeMap<K, V, S> where BTreeMap<K, V>: Eq {}
+
+impl<K, V, S> MaxEncodedLen for BoundedBTreeMap<K, V, S>
+where
+	K: MaxEncodedLen,
+	V: MaxEncodedLen,
+	S: Get<u32>,
+	BoundedBTreeMap<K, V, S>: Encode,
+{
+	fn max_encoded_len() -> usize {
+		// BoundedBTreeMap<K, V, S> encodes like a Vec<(K, V)>, which encodes like a compact u32
+		// plus each item (k, v) in the map:
+		// https://substrate.dev/rustdocs/v3.0.0/src/parity_scale_codec/codec.rs.html#798-808
+		codec::Compact(S::get())
+			.encoded_size()
+			.saturating_add(Self::bound().saturating_mul(K::max_encoded_len().saturating_add(V::max_encoded_len())))
+	}
+}
+
+#[cfg(test)]
+pub mod test {
+	use super::*;
+	use sp_std::convert::TryInto;
+
+	crate::parameter_types! {
+		pub const Seven: u32 = 7;
+	}
+
+	#[test]
+	fn try_insert_works() {
+		let mut bounded: BoundedBTreeMap<u32, (), Seven> =
+			[1, 2, 3, 4, 5, 6].iter().map(|k| (*k, ())).collect::<BTreeMap<_, _>>().try_into().unwrap();
+		bounded.try_insert(7, ()).unwrap();
+		assert_eq!(bounded.len(), 7);
+
+		assert!(bounded.try_insert(8, ()).is_err());
+		assert_eq!(bounded.len(), 7);
+	}
+
+	#[test]
+	fn try_insert_overwrites_at_capacity() {
+		let mut bounded: BoundedBTreeMap<u32, u32, Seven> = [1, 2, 3, 4, 5, 6, 7]
+			.iter()
+			.map(|k| (*k, *k))
+			.collect::<BTreeMap<_, _>>()
+			.try_into()
+			.unwrap();
+
+		assert_eq!(bounded.try_insert(7, 100).unwrap(), Some(7));
+		assert_eq!(*bounded.get(&7).unwrap(), 100);
+		assert_eq!(bounded.len(), 7);
+	}
+
+	#[test]
+	fn too_big_fail_to_decode() {
+		let v: Vec<(u32, u32)> = vec![(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 6), (7, 7), (8, 8)];
+		let encoded = v.encode();
+		assert_eq!(
+			BoundedBTreeMap::<u32, u32, Seven>::decode(&mut &encoded[..]),
+			Err("BoundedBTreeMap exceeds its limit".into()),
+		);
+	}
+}
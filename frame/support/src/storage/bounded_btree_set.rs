@@ -0,0 +1,225 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits, types and structs to support a bounded `BTreeSet`.
+
+use sp_std::prelude::*;
+use sp_std::{collections::btree_set::BTreeSet, convert::TryFrom, marker::PhantomData};
+use codec::{Encode, Decode};
+use core::ops::Deref;
+use crate::traits::{Get, MaxEncodedLen};
+
+/// A bounded set based on a B-Tree.
+///
+/// B-Trees represent a fundamentally different data structure than a `Vec`. They guarantee that
+/// their contents are sorted according to the ordering of their `T` type, rather than insertion
+/// order, and that lookups for a specific value are O(log n).
+///
+/// Unlike a normal `BTreeSet`, the number of values is bounded by `S`: the bound is enforced by
+/// `try_insert`, `TryFrom` and `Decode`. Read-only operations are deferred to the inner `BTreeSet`
+/// through `Deref`.
+#[derive(Encode)]
+pub struct BoundedBTreeSet<T, S>(BTreeSet<T>, PhantomData<S>);
+
+impl<T, S> Decode for BoundedBTreeSet<T, S>
+where
+	T: Decode + Ord,
+	S: Get<u32>,
+{
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		// Start by reading the compact-encoded length prefix, exactly as `BTreeSet<T>` would, so
+		// we can reject an over-long encoding before allocating or decoding a single element.
+		let len = <codec::Compact<u32>>::decode(input)?.0 as usize;
+		if len > Self::bound() {
+			return Err("BoundedBTreeSet exceeds its limit".into())
+		}
+
+		let mut inner = BTreeSet::new();
+		for _ in 0..len {
+			inner.insert(T::decode(input)?);
+		}
+		Ok(unsafe { Self::unchecked_from(inner) })
+	}
+}
+
+impl<T, S> BoundedBTreeSet<T, S> {
+	/// Create `Self` from `t` without any checks.
+	unsafe fn unchecked_from(t: BTreeSet<T>) -> Self {
+		Self(t, Default::default())
+	}
+
+	/// Consume self, and return the inner `BTreeSet`. Henceforth, the `BTreeSet<_>` can be
+	/// altered in an arbitrary way. At some point, if the reverse conversion is required, `TryFrom`
+	/// can be used.
+	///
+	/// This is useful for cases if you need access to an internal API of the inner `BTreeSet<_>`
+	/// which is not provided by the wrapper `BoundedBTreeSet`.
+	pub fn into_inner(self) -> BTreeSet<T> {
+		self.0
+	}
+}
+
+impl<T: Ord, S: Get<u32>> BoundedBTreeSet<T, S> {
+	/// Get the bound of the type in `usize`.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Exactly the same semantics as `BTreeSet::insert`, but returns an `Err` (and is a noop) if
+	/// the new length of the set exceeds `S` while inserting a *new* value.
+	///
+	/// Note that a `true` result means that the item was inserted and `false` means that the
+	/// item was already present, and the set was left untouched.
+	pub fn try_insert(&mut self, value: T) -> Result<bool, ()> {
+		if self.0.len() < Self::bound() || self.0.contains(&value) {
+			Ok(self.0.insert(value))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T, S> Default for BoundedBTreeSet<T, S> {
+	fn default() -> Self {
+		// the bound cannot be below 0, which is satisfied by an empty set
+		unsafe { Self::unchecked_from(BTreeSet::default()) }
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T, S> sp_std::fmt::Debug for BoundedBTreeSet<T, S>
+where
+	BTreeSet<T>: sp_std::fmt::Debug,
+	S: Get<u32>,
+{
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+		f.debug_tuple("BoundedBTreeSet").field(&self.0).field(&S::get()).finish()
+	}
+}
+
+impl<T, S> Clone for BoundedBTreeSet<T, S>
+where
+	BTreeSet<T>: Clone,
+{
+	fn clone(&self) -> Self {
+		// bound is retained
+		unsafe { Self::unchecked_from(self.0.clone()) }
+	}
+}
+
+impl<T, S: Get<u32>> TryFrom<BTreeSet<T>> for BoundedBTreeSet<T, S> {
+	type Error = ();
+	fn try_from(t: BTreeSet<T>) -> Result<Self, Self::Error> {
+		if t.len() <= Self::bound() {
+			// explicit check just above
+			Ok(unsafe { Self::unchecked_from(t) })
+		} else {
+			Err(())
+		}
+	}
+}
+
+// It is okay to give a non-mutable reference of the inner set to anyone.
+impl<T, S> AsRef<BTreeSet<T>> for BoundedBTreeSet<T, S> {
+	fn as_ref(&self) -> &BTreeSet<T> {
+		&self.0
+	}
+}
+
+// will allow for immutable all operations of `BTreeSet<T>` on `BoundedBTreeSet<T>`.
+impl<T, S> Deref for BoundedBTreeSet<T, S> {
+	type Target = BTreeSet<T>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T, S> sp_std::iter::IntoIterator for BoundedBTreeSet<T, S> {
+	type Item = T;
+	type IntoIter = sp_std::collections::btree_set::IntoIter<T>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<T, S> PartialEq for BoundedBTreeSet<T, S>
+where
+	BTreeSet<T>: PartialEq,
+{
+	fn eq(&self, rhs: &Self) -> bool {
+		self.0 == rhs.0
+	}
+}
+
+impl<T, S> Eq for BoundedBTreeSet<T, S> where BTreeSet<T>: Eq {}
+
+impl<T, S> MaxEncodedLen for BoundedBTreeSet<T, S>
+where
+	T: MaxEncodedLen,
+	S: Get<u32>,
+	BoundedBTreeSet<T, S>: Encode,
+{
+	fn max_encoded_len() -> usize {
+		// BoundedBTreeSet<T, S> encodes like a Vec<T>, which encodes like a compact u32 plus
+		// each item in the set:
+		// https://substrate.dev/rustdocs/v3.0.0/src/parity_scale_codec/codec.rs.html#798-808
+		codec::Compact(S::get())
+			.encoded_size()
+			.saturating_add(Self::bound().saturating_mul(T::max_encoded_len()))
+	}
+}
+
+#[cfg(test)]
+pub mod test {
+	use super::*;
+	use sp_std::convert::TryInto;
+
+	crate::parameter_types! {
+		pub const Seven: u32 = 7;
+	}
+
+	#[test]
+	fn try_insert_works() {
+		let mut bounded: BoundedBTreeSet<u32, Seven> =
+			[1, 2, 3, 4, 5, 6].iter().copied().collect::<BTreeSet<_>>().try_into().unwrap();
+		bounded.try_insert(7).unwrap();
+		assert_eq!(bounded.len(), 7);
+
+		assert!(bounded.try_insert(8).is_err());
+		assert_eq!(bounded.len(), 7);
+	}
+
+	#[test]
+	fn try_insert_existing_value_is_noop_at_capacity() {
+		let mut bounded: BoundedBTreeSet<u32, Seven> =
+			[1, 2, 3, 4, 5, 6, 7].iter().copied().collect::<BTreeSet<_>>().try_into().unwrap();
+
+		assert_eq!(bounded.try_insert(7).unwrap(), false);
+		assert_eq!(bounded.len(), 7);
+	}
+
+	#[test]
+	fn too_big_fail_to_decode() {
+		let v: Vec<u32> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+		let encoded = v.encode();
+		assert_eq!(
+			BoundedBTreeSet::<u32, Seven>::decode(&mut &encoded[..]),
+			Err("BoundedBTreeSet exceeds its limit".into()),
+		);
+	}
+}
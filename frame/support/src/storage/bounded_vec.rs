@@ -37,7 +37,7 @@ use crate::{
 ///
 /// As the name suggests, the length of the queue is always bounded. All internal operations ensure
 /// this bound is respected.
-#[derive(Encode, Decode)]
+#[derive(Encode)]
 pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
 
 impl<T, S> BoundedVec<T, S> {
@@ -144,6 +144,92 @@ impl<T, S: Get<u32>> BoundedVec<T, S> {
 	}
 }
 
+impl<T: Ord, S: Get<u32>> BoundedVec<T, S> {
+	/// Insert `element` into `self`, maintaining the sort order of the existing elements.
+	///
+	/// This correctly assumes that `self` is already sorted, which is guaranteed as long as `self`
+	/// is only ever mutated through the sorted operations in this `impl` block.
+	///
+	/// Returns `Ok(true)` if `element` was inserted, `Ok(false)` if `element` already existed and
+	/// was therefore left untouched, and `Err(())` if `self` is already at [`Self::bound`].
+	pub fn try_insert_sorted(&mut self, element: T) -> Result<bool, ()> {
+		match self.0.binary_search(&element) {
+			// already present: a no-op, regardless of whether `self` is full.
+			Ok(_) => Ok(false),
+			Err(index) => {
+				if self.len() >= Self::bound() {
+					return Err(())
+				}
+				self.0.insert(index, element);
+				Ok(true)
+			},
+		}
+	}
+
+	/// Insert `element`, maintaining the sort order, even if `self` is already at
+	/// [`Self::bound`].
+	///
+	/// If inserting `element` would exceed the bound, the largest (rightmost) element is evicted
+	/// to make room, and is returned as `Ok(Some(evicted))`. This is useful for maintaining a
+	/// bounded "top-N smallest" set. If `element` itself would have been the evicted element (it
+	/// sorts greater than everything already present while `self` is full), it is returned
+	/// unchanged as `Ok(Some(element))` and `self` is left untouched. If `element` was inserted
+	/// without evicting anything, or was already present (leaving `self` untouched), `Ok(None)` is
+	/// returned.
+	pub fn force_insert_keep_left(&mut self, element: T) -> Result<Option<T>, ()> {
+		// Exactly the same semantics as `force_insert_keep_right`, but the element kicked out is
+		// the largest one, to keep the "left" (smallest) elements in.
+		if Self::bound() == 0 {
+			return Err(())
+		}
+		let insert_index = match self.0.binary_search(&element) {
+			Ok(_) => return Ok(None),
+			Err(index) => index,
+		};
+		if self.len() < Self::bound() {
+			self.0.insert(insert_index, element);
+			Ok(None)
+		} else if insert_index < self.len() {
+			self.0.insert(insert_index, element);
+			Ok(self.0.pop())
+		} else {
+			// `element` sorts at, or beyond, the end of an already-full vector: it is itself the
+			// one that would be evicted, so leave `self` untouched.
+			Ok(Some(element))
+		}
+	}
+
+	/// Insert `element`, maintaining the sort order, even if `self` is already at
+	/// [`Self::bound`].
+	///
+	/// If inserting `element` would exceed the bound, the smallest (leftmost) element is evicted
+	/// to make room, and is returned as `Ok(Some(evicted))`. If `element` itself would have been
+	/// the evicted element (it sorts less than everything already present while `self` is full),
+	/// it is returned unchanged as `Ok(Some(element))` and `self` is left untouched. If `element`
+	/// was inserted without evicting anything, or was already present (leaving `self` untouched),
+	/// `Ok(None)` is returned.
+	pub fn force_insert_keep_right(&mut self, element: T) -> Result<Option<T>, ()> {
+		if Self::bound() == 0 {
+			return Err(())
+		}
+		let insert_index = match self.0.binary_search(&element) {
+			Ok(_) => return Ok(None),
+			Err(index) => index,
+		};
+		if self.len() < Self::bound() {
+			self.0.insert(insert_index, element);
+			Ok(None)
+		} else if insert_index > 0 {
+			self.0.insert(insert_index, element);
+			Ok(Some(self.0.remove(0)))
+		} else {
+			// `element` sorts at, or before, the start of an already-full vector: it is itself
+			// the one that would be evicted, so leave `self` untouched.
+			Ok(Some(element))
+		}
+	}
+}
+
 impl<T, S> Default for BoundedVec<T, S> {
 	fn default() -> Self {
 		// the bound cannot be below 0, which is satisfied by an empty vector
@@ -184,6 +270,23 @@ impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
 	}
 }
 
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		// Start by reading the compact-encoded length prefix, exactly as `Vec<T>` would, so we
+		// can reject an over-long encoding before allocating or decoding a single element.
+		let len = <codec::Compact<u32>>::decode(input)?.0 as usize;
+		if len > Self::bound() {
+			return Err("BoundedVec exceeds its limit".into())
+		}
+
+		let mut inner = Vec::with_capacity(sp_std::cmp::min(len, Self::bound()));
+		for _ in 0..len {
+			inner.push(T::decode(input)?);
+		}
+		Ok(unsafe { Self::unchecked_from(inner) })
+	}
+}
+
 // It is okay to give a non-mutable reference of the inner vec to anyone.
 impl<T, S> AsRef<Vec<T>> for BoundedVec<T, S> {
 	fn as_ref(&self) -> &Vec<T> {
@@ -273,8 +376,308 @@ impl<T, S> Eq for BoundedVec<T, S> where T: Eq {}
 
 impl<T, S> StorageDecodeLength for BoundedVec<T, S> {}
 
+impl<T, S> MaxEncodedLen for BoundedVec<T, S>
+where
+	T: MaxEncodedLen,
+	S: Get<u32>,
+	BoundedVec<T, S>: Encode,
+{
+	fn max_encoded_len() -> usize {
+		// BoundedVec<T, S> encodes like Vec<T> which encodes like [T], which is a compact u32
+		// plus each item in the slice:
+		// https://substrate.dev/rustdocs/v3.0.0/src/parity_scale_codec/codec.rs.html#798-808
+		codec::Compact(S::get())
+			.encoded_size()
+			.saturating_add(Self::bound().saturating_mul(T::max_encoded_len()))
+	}
+}
+
+/// A weakly bounded vector.
+///
+/// It has implementations for efficient append and length decoding, as with a normal `Vec<_>`,
+/// once put into storage as a raw value, map or double-map.
+///
+/// Unlike a standard [`BoundedVec`], this type will not panic or strictly enforce the bound over
+/// its length during construction or decoding. It is instead just a marker of how it *should* be
+/// used, with all of the growing operations (`try_push`, `try_insert`, ...) still bound-checked.
+/// This is useful when the bound of a type may change while existing storage still needs to be
+/// decoded without loss, such as a runtime upgrade that shrinks the bound of an existing storage
+/// item: forcing a strict `TryFrom`/`Decode` at that point would make it impossible to decode the
+/// longer `Vec<T>` that is already in storage, bricking the chain.
+///
+/// Unlike [`BoundedVec`], this type can be converted into from a `Vec<T>` of any length, but the
+/// bound is enforced on all growing operations, and emits a warning (via `log::warn!`) if the
+/// bound is violated upon construction.
+pub struct WeakBoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S> WeakBoundedVec<T, S> {
+	/// Create `Self` from `t` without any checks.
+	unsafe fn unchecked_from(t: Vec<T>) -> Self {
+		Self(t, Default::default())
+	}
+
+	/// Consume self, and return the inner `Vec`. Henceforth, the `Vec<_>` can be altered in an
+	/// arbitrary way. At some point, if the reverse conversion is required, `TryFrom<Vec<_>>` can
+	/// be used.
+	///
+	/// This is useful for cases if you need access to an internal API of the inner `Vec<_>` which
+	/// is not provided by the wrapper `WeakBoundedVec`.
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+
+	/// Exactly the same semantics as [`Vec::remove`].
+	///
+	/// # Panics
+	///
+	/// Panics if `index` is out of bounds.
+	pub fn remove(&mut self, index: usize) {
+		self.0.remove(index);
+	}
+
+	/// Exactly the same semantics as [`Vec::swap_remove`].
+	///
+	/// # Panics
+	///
+	/// Panics if `index` is out of bounds.
+	pub fn swap_remove(&mut self, index: usize) {
+		self.0.swap_remove(index);
+	}
+
+	/// Exactly the same semantics as [`Vec::retain`].
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+		self.0.retain(f)
+	}
+}
+
+impl<T, S: Get<u32>> WeakBoundedVec<T, S> {
+	/// Get the bound of the type in `usize`.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Create `Self` from `t` without any checks. Logs warnings if the bound is not being
+	/// respected. The additional scope can be used to indicate where a potential overflow is
+	/// happening.
+	pub fn force_from(t: Vec<T>, scope: Option<&'static str>) -> Self {
+		if t.len() > Self::bound() {
+			log::warn!(
+				target: crate::LOG_TARGET,
+				"length of a weak bounded vector in scope {} is not respected.",
+				scope.unwrap_or("UNKNOWN"),
+			);
+		}
+
+		unsafe { Self::unchecked_from(t) }
+	}
+
+	/// Consumes self and mutates self via the given `mutate` function.
+	///
+	/// If the outcome of mutation is within bounds, `Some(Self)` is returned. Else, `None` is
+	/// returned.
+	///
+	/// Unlike [`Self::try_from`], which accepts a `Vec<T>` of any length, this checks the bound
+	/// on the mutated vector.
+	pub fn try_mutate(mut self, mut mutate: impl FnMut(&mut Vec<T>)) -> Option<Self> {
+		mutate(&mut self.0);
+		(self.0.len() <= Self::bound()).then(move || self)
+	}
+
+	/// Exactly the same semantics as [`Vec::insert`], but returns an `Err` (and is a noop) if the
+	/// new length of the vector exceeds `S`.
+	///
+	/// # Panics
+	///
+	/// Panics if `index > len`.
+	pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), ()> {
+		if self.len() < Self::bound() {
+			self.0.insert(index, element);
+			Ok(())
+		} else {
+			Err(())
+		}
+	}
+
+	/// Exactly the same semantics as [`Vec::push`], but returns an `Err` (and is a noop) if the
+	/// new length of the vector exceeds `S`.
+	///
+	/// # Panics
+	///
+	/// Panics if the new capacity exceeds isize::MAX bytes.
+	pub fn try_push(&mut self, element: T) -> Result<(), ()> {
+		if self.len() < Self::bound() {
+			self.0.push(element);
+			Ok(())
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T, S> Default for WeakBoundedVec<T, S> {
+	fn default() -> Self {
+		// the bound cannot be below 0, which is satisfied by an empty vector
+		unsafe { Self::unchecked_from(Vec::default()) }
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T, S> fmt::Debug for WeakBoundedVec<T, S>
+where
+	T: fmt::Debug,
+	S: Get<u32>,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("WeakBoundedVec").field(&self.0).field(&Self::bound()).finish()
+	}
+}
+
+impl<T, S> Clone for WeakBoundedVec<T, S>
+where
+	T: Clone,
+{
+	fn clone(&self) -> Self {
+		// bound is retained
+		unsafe { Self::unchecked_from(self.0.clone()) }
+	}
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for WeakBoundedVec<T, S> {
+	// always `Ok`: a `WeakBoundedVec` tolerates an over-long input at construction time, and only
+	// enforces the bound on the operations that grow it.
+	type Error = ();
+	fn try_from(t: Vec<T>) -> Result<Self, Self::Error> {
+		Ok(Self::force_from(t, Some("WeakBoundedVec::try_from")))
+	}
+}
+
+impl<T: Encode, S> Encode for WeakBoundedVec<T, S> {
+	fn encode(&self) -> Vec<u8> {
+		self.0.encode()
+	}
+}
+
+impl<T: Decode, S: Get<u32>> Decode for WeakBoundedVec<T, S> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		// `WeakBoundedVec<T, S>` stores a `Vec<T>`, so we can decode that directly and then warn
+		// (but keep the data) if the bound is not respected, rather than failing the decode.
+		let inner = Vec::<T>::decode(input)?;
+		Ok(Self::force_from(inner, Some("WeakBoundedVec::decode")))
+	}
+}
+
+impl<T: Encode, S> EncodeLike for WeakBoundedVec<T, S> {}
+
+impl<T: Encode, S> EncodeLike<Vec<T>> for WeakBoundedVec<T, S> {}
+
+// It is okay to give a non-mutable reference of the inner vec to anyone.
+impl<T, S> AsRef<Vec<T>> for WeakBoundedVec<T, S> {
+	fn as_ref(&self) -> &Vec<T> {
+		&self.0
+	}
+}
+
+impl<T, S> AsRef<[T]> for WeakBoundedVec<T, S> {
+	fn as_ref(&self) -> &[T] {
+		&self.0
+	}
+}
+
+impl<T, S> AsMut<[T]> for WeakBoundedVec<T, S> {
+	fn as_mut(&mut self) -> &mut [T] {
+		&mut self.0
+	}
+}
+
+// will allow for immutable all operations of `Vec<T>` on `WeakBoundedVec<T>`.
+impl<T, S> Deref for WeakBoundedVec<T, S> {
+	type Target = Vec<T>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+// Allows for indexing similar to a normal `Vec`. Can panic if out of bound.
+impl<T, S, I> Index<I> for WeakBoundedVec<T, S>
+where
+	I: SliceIndex<[T]>,
+{
+	type Output = I::Output;
+
+	#[inline]
+	fn index(&self, index: I) -> &Self::Output {
+		self.0.index(index)
+	}
+}
+
+impl<T, S, I> IndexMut<I> for WeakBoundedVec<T, S>
+where
+	I: SliceIndex<[T]>,
+{
+	#[inline]
+	fn index_mut(&mut self, index: I) -> &mut Self::Output {
+		self.0.index_mut(index)
+	}
+}
+
+impl<T, S> sp_std::iter::IntoIterator for WeakBoundedVec<T, S> {
+	type Item = T;
+	type IntoIter = sp_std::vec::IntoIter<T>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<T, S> codec::DecodeLength for WeakBoundedVec<T, S> {
+	fn len(self_encoded: &[u8]) -> Result<usize, codec::Error> {
+		// `WeakBoundedVec<T, _>` stored just a `Vec<T>`, thus the length is at the beginning in
+		// `Compact` form, and same implementation as `Vec<T>` can be used.
+		<Vec<T> as codec::DecodeLength>::len(self_encoded)
+	}
+}
+
+impl<T, S> PartialEq for WeakBoundedVec<T, S>
+where
+	T: PartialEq,
+{
+	fn eq(&self, rhs: &Self) -> bool {
+		self.0 == rhs.0
+	}
+}
+
+impl<T: PartialEq, S: Get<u32>> PartialEq<Vec<T>> for WeakBoundedVec<T, S> {
+	fn eq(&self, other: &Vec<T>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<T, S> Eq for WeakBoundedVec<T, S> where T: Eq {}
+
+impl<T, S> StorageDecodeLength for WeakBoundedVec<T, S> {}
+
+impl<T, S> MaxEncodedLen for WeakBoundedVec<T, S>
+where
+	T: MaxEncodedLen,
+	S: Get<u32>,
+	WeakBoundedVec<T, S>: Encode,
+{
+	fn max_encoded_len() -> usize {
+		// WeakBoundedVec<T, S> encodes like Vec<T> which encodes like [T], which is a compact u32
+		// plus each item in the slice:
+		// https://substrate.dev/rustdocs/v3.0.0/src/parity_scale_codec/codec.rs.html#798-808
+		codec::Compact(S::get())
+			.encoded_size()
+			.saturating_add(Self::bound().saturating_mul(T::max_encoded_len()))
+	}
+}
+
 /// Storage value that is *maybe* capable of [`StorageAppend`](crate::storage::StorageAppend).
-pub trait TryAppendValue<T: Encode, S: Get<u32>> {
+///
+/// `B` is the bounded wrapper type actually stored (e.g. [`BoundedVec<T, S>`] or
+/// [`WeakBoundedVec<T, S>`]), so this one trait can be implemented generically for every such
+/// wrapper.
+pub trait TryAppendValue<B, T: Encode, S: Get<u32>> {
 	/// Try and append the `item` into the storage item.
 	///
 	/// This might fail if bounds are not respected.
@@ -282,7 +685,11 @@ pub trait TryAppendValue<T: Encode, S: Get<u32>> {
 }
 
 /// Storage map that is *maybe* capable of [`StorageAppend`](crate::storage::StorageAppend).
-pub trait TryAppendMap<K: FullCodec, T: Encode, S: Get<u32>> {
+///
+/// `B` is the bounded wrapper type actually stored (e.g. [`BoundedVec<T, S>`] or
+/// [`WeakBoundedVec<T, S>`]), so this one trait can be implemented generically for every such
+/// wrapper.
+pub trait TryAppendMap<B, K: FullCodec, T: Encode, S: Get<u32>> {
 	/// Try and append the `item` into the storage map at the given `key`.
 	///
 	/// This might fail if bounds are not respected.
@@ -293,7 +700,11 @@ pub trait TryAppendMap<K: FullCodec, T: Encode, S: Get<u32>> {
 }
 
 /// Storage double map that is *maybe* capable of [`StorageAppend`](crate::storage::StorageAppend).
-pub trait TryAppendDoubleMap<K1: FullCodec, K2: FullCodec, T: Encode, S: Get<u32>> {
+///
+/// `B` is the bounded wrapper type actually stored (e.g. [`BoundedVec<T, S>`] or
+/// [`WeakBoundedVec<T, S>`]), so this one trait can be implemented generically for every such
+/// wrapper.
+pub trait TryAppendDoubleMap<B, K1: FullCodec, K2: FullCodec, T: Encode, S: Get<u32>> {
 	/// Try and append the `item` into the storage double map at the given `key`.
 	///
 	/// This might fail if bounds are not respected.
@@ -308,60 +719,82 @@ pub trait TryAppendDoubleMap<K1: FullCodec, K2: FullCodec, T: Encode, S: Get<u32
 	) -> Result<(), ()>;
 }
 
-impl<T, S, StorageValueT> TryAppendValue<T, S> for StorageValueT
+// Shared by every bounded-vector-like storage value/map/double-map, so that `BoundedVec` and
+// `WeakBoundedVec` do not have to duplicate the bound-check-then-append logic.
+fn append_bounded<T: Encode, LikeT: EncodeLike<T>>(
+	key: &[u8],
+	item: LikeT,
+	bound: usize,
+	current: usize,
+) -> Result<(), ()> {
+	if current < bound {
+		// NOTE: we cannot reuse the implementation for `Vec<T>` here because we never want to
+		// mark a bounded vector as `StorageAppend`.
+		sp_io::storage::append(key, item.encode());
+		Ok(())
+	} else {
+		Err(())
+	}
+}
+
+/// A marker trait implemented by every "bounded vector"-like wrapper around `Vec<T>` (currently
+/// [`BoundedVec`] and [`WeakBoundedVec`]), tying the wrapper to its element type `T` and its bound
+/// `S`.
+///
+/// This exists solely so that [`TryAppendValue`], [`TryAppendMap`] and [`TryAppendDoubleMap`] can
+/// each be implemented once, generically over whichever bounded wrapper a given storage item
+/// happens to use, rather than once per wrapper type.
+pub trait StorageTryAppend<T, S: Get<u32>> {
+	/// The maximum length that `Self` can take.
+	fn bound() -> usize {
+		S::get() as usize
+	}
+}
+
+impl<T, S: Get<u32>> StorageTryAppend<T, S> for BoundedVec<T, S> {}
+
+impl<T, S: Get<u32>> StorageTryAppend<T, S> for WeakBoundedVec<T, S> {}
+
+impl<T, S, B, StorageValueT> TryAppendValue<B, T, S> for StorageValueT
 where
-	BoundedVec<T, S>: FullCodec,
+	B: StorageTryAppend<T, S> + FullCodec,
 	T: Encode,
 	S: Get<u32>,
-	StorageValueT: generator::StorageValue<BoundedVec<T, S>>,
+	StorageValueT: generator::StorageValue<B>,
 {
 	fn try_append<LikeT: EncodeLike<T>>(item: LikeT) -> Result<(), ()> {
-		let bound = BoundedVec::<T, S>::bound();
+		let bound = B::bound();
 		let current = Self::decode_len().unwrap_or_default();
-		if current < bound {
-			// NOTE: we cannot reuse the implementation for `Vec<T>` here because we never want to
-			// mark `BoundedVec<T, S>` as `StorageAppend`.
-			let key = Self::storage_value_final_key();
-			sp_io::storage::append(&key, item.encode());
-			Ok(())
-		} else {
-			Err(())
-		}
+		append_bounded::<T, _>(&Self::storage_value_final_key(), item, bound, current)
 	}
 }
 
-impl<K, T, S, StorageMapT> TryAppendMap<K, T, S> for StorageMapT
+impl<K, T, S, B, StorageMapT> TryAppendMap<B, K, T, S> for StorageMapT
 where
 	K: FullCodec,
-	BoundedVec<T, S>: FullCodec,
+	B: StorageTryAppend<T, S> + FullCodec,
 	T: Encode,
 	S: Get<u32>,
-	StorageMapT: generator::StorageMap<K, BoundedVec<T, S>>,
+	StorageMapT: generator::StorageMap<K, B>,
 {
 	fn try_append<LikeK: EncodeLike<K> + Clone, LikeT: EncodeLike<T>>(
 		key: LikeK,
 		item: LikeT,
 	) -> Result<(), ()> {
-		let bound = BoundedVec::<T, S>::bound();
+		let bound = B::bound();
 		let current = Self::decode_len(key.clone()).unwrap_or_default();
-		if current < bound {
-			let key = Self::storage_map_final_key(key);
-			sp_io::storage::append(&key, item.encode());
-			Ok(())
-		} else {
-			Err(())
-		}
+		append_bounded::<T, _>(&Self::storage_map_final_key(key), item, bound, current)
 	}
 }
 
-impl<K1, K2, T, S, StorageDoubleMapT> TryAppendDoubleMap<K1, K2, T, S> for StorageDoubleMapT
+impl<K1, K2, T, S, B, StorageDoubleMapT> TryAppendDoubleMap<B, K1, K2, T, S> for StorageDoubleMapT
 where
 	K1: FullCodec,
 	K2: FullCodec,
-	BoundedVec<T, S>: FullCodec,
+	B: StorageTryAppend<T, S> + FullCodec,
 	T: Encode,
 	S: Get<u32>,
-	StorageDoubleMapT: generator::StorageDoubleMap<K1, K2, BoundedVec<T, S>>,
+	StorageDoubleMapT: generator::StorageDoubleMap<K1, K2, B>,
 {
 	fn try_append<
 		LikeK1: EncodeLike<K1> + Clone,
@@ -372,31 +805,9 @@ where
 		key2: LikeK2,
 		item: LikeT,
 	) -> Result<(), ()> {
-		let bound = BoundedVec::<T, S>::bound();
+		let bound = B::bound();
 		let current = Self::decode_len(key1.clone(), key2.clone()).unwrap_or_default();
-		if current < bound {
-			let double_map_key = Self::storage_double_map_final_key(key1, key2);
-			sp_io::storage::append(&double_map_key, item.encode());
-			Ok(())
-		} else {
-			Err(())
-		}
-	}
-}
-
-impl<T, S> MaxEncodedLen for BoundedVec<T, S>
-where
-	T: MaxEncodedLen,
-	S: Get<u32>,
-	BoundedVec<T, S>: Encode,
-{
-	fn max_encoded_len() -> usize {
-		// BoundedVec<T, S> encodes like Vec<T> which encodes like [T], which is a compact u32
-		// plus each item in the slice:
-		// https://substrate.dev/rustdocs/v3.0.0/src/parity_scale_codec/codec.rs.html#798-808
-		codec::Compact(S::get())
-			.encoded_size()
-			.saturating_add(Self::bound().saturating_mul(T::max_encoded_len()))
+		append_bounded::<T, _>(&Self::storage_double_map_final_key(key1, key2), item, bound, current)
 	}
 }
 
@@ -559,4 +970,80 @@ pub mod test {
 		let bounded: BoundedVec<u32, Seven> = vec![1, 2, 3, 4, 5, 6].try_into().unwrap();
 		assert_eq!(bounded, vec![1, 2, 3, 4, 5, 6]);
 	}
+
+	#[test]
+	fn decode_too_long_fails() {
+		let v: Vec<u32> = vec![1, 2, 3, 4, 5, 6, 7, 8];
+		assert_eq!(v.len(), Seven::get() as usize + 1);
+		let encoded = v.encode();
+		assert!(BoundedVec::<u32, Seven>::decode(&mut &encoded[..]).is_err());
+	}
+
+	#[test]
+	fn decode_within_bound_works() {
+		let v: Vec<u32> = vec![1, 2, 3, 4, 5, 6, 7];
+		assert_eq!(v.len(), Seven::get() as usize);
+		let encoded = v.encode();
+		let bounded = BoundedVec::<u32, Seven>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(*bounded, v);
+	}
+
+	#[test]
+	fn try_insert_sorted_works() {
+		let mut bounded: BoundedVec<u32, Seven> = vec![1, 3, 5, 7].try_into().unwrap();
+		assert_eq!(bounded.try_insert_sorted(4).unwrap(), true);
+		assert_eq!(*bounded, vec![1, 3, 4, 5, 7]);
+
+		// duplicates are a no-op, without modifying the vector.
+		assert_eq!(bounded.try_insert_sorted(4).unwrap(), false);
+		assert_eq!(*bounded, vec![1, 3, 4, 5, 7]);
+
+		assert_ok!(bounded.try_insert_sorted(6));
+		assert_eq!(*bounded, vec![1, 3, 4, 5, 6, 7]);
+
+		assert_ok!(bounded.try_insert_sorted(2));
+		assert_eq!(*bounded, vec![1, 2, 3, 4, 5, 6, 7]);
+
+		// already at `Seven::get() == 7`, a genuinely new element must be rejected.
+		assert!(bounded.try_insert_sorted(100).is_err());
+		assert_eq!(*bounded, vec![1, 2, 3, 4, 5, 6, 7]);
+
+		// but a duplicate is still a no-op success, even while full.
+		assert_eq!(bounded.try_insert_sorted(4).unwrap(), false);
+		assert_eq!(*bounded, vec![1, 2, 3, 4, 5, 6, 7]);
+	}
+
+	#[test]
+	fn force_insert_keep_left_works() {
+		let mut bounded: BoundedVec<u32, Four> = vec![1, 3, 5, 7].try_into().unwrap();
+
+		// a new, smaller element evicts the largest one.
+		assert_eq!(bounded.force_insert_keep_left(4).unwrap(), Some(7));
+		assert_eq!(*bounded, vec![1, 3, 4, 5]);
+
+		// an element that would itself be the evicted one is returned unchanged.
+		assert_eq!(bounded.force_insert_keep_left(100).unwrap(), Some(100));
+		assert_eq!(*bounded, vec![1, 3, 4, 5]);
+
+		// duplicates are a noop.
+		assert_eq!(bounded.force_insert_keep_left(4).unwrap(), None);
+		assert_eq!(*bounded, vec![1, 3, 4, 5]);
+	}
+
+	#[test]
+	fn force_insert_keep_right_works() {
+		let mut bounded: BoundedVec<u32, Four> = vec![1, 3, 5, 7].try_into().unwrap();
+
+		// a new, larger element evicts the smallest one.
+		assert_eq!(bounded.force_insert_keep_right(4).unwrap(), Some(1));
+		assert_eq!(*bounded, vec![3, 4, 5, 7]);
+
+		// an element that would itself be the evicted one is returned unchanged.
+		assert_eq!(bounded.force_insert_keep_right(0).unwrap(), Some(0));
+		assert_eq!(*bounded, vec![3, 4, 5, 7]);
+
+		// duplicates are a noop.
+		assert_eq!(bounded.force_insert_keep_right(4).unwrap(), None);
+		assert_eq!(*bounded, vec![3, 4, 5, 7]);
+	}
 }